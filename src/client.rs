@@ -0,0 +1,193 @@
+// Rust JSON-RPC Library
+// Written in 2015 by
+//     Andrew Poelstra <apoelstra@wpsoftware.net>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use strason::Json;
+
+#[cfg(unix)]
+use std::path::PathBuf;
+
+use error::Error;
+use transport::{HttpTransport, Transport};
+#[cfg(unix)]
+use transport::UnixSocketTransport;
+use {ids_match, normalize_id, Request, Response};
+
+/// A handle to a remote JSONRPC server, generic over the [`Transport`] used
+/// to reach it
+pub struct Client<T> {
+    transport: T,
+    nonce: Arc<Mutex<u64>>
+}
+
+impl Client<HttpTransport> {
+    /// Creates a new client that talks to the server over HTTP
+    pub fn new<U, P>(url: &str, user: U, pass: P) -> Client<HttpTransport>
+    where
+        U: Into<Option<String>>,
+        P: Into<Option<String>>,
+    {
+        Client::with_transport(HttpTransport::new(url, user, pass))
+    }
+}
+
+#[cfg(unix)]
+impl Client<UnixSocketTransport> {
+    /// Creates a new client that talks to a daemon over a Unix domain
+    /// socket, such as the RPC socket exposed by c-lightning
+    pub fn from_unix_socket<P: Into<PathBuf>>(path: P) -> Client<UnixSocketTransport> {
+        Client::with_transport(UnixSocketTransport::new(path))
+    }
+}
+
+impl<T: Transport> Client<T> {
+    /// Creates a new client that sends requests over the given transport
+    pub fn with_transport(transport: T) -> Client<T> {
+        Client {
+            transport: transport,
+            nonce: Arc::new(Mutex::new(0))
+        }
+    }
+
+    /// Sends a request to the server and waits for its response
+    pub fn execute<'a>(&self, request: Request<'a>) -> Result<Response, Error> {
+        let response = self.transport.send_request(&request)?;
+        match response.jsonrpc {
+            Some(ref jsonrpc) if &*jsonrpc == "2.0" => {}
+            _ => return Err(Error::VersionMismatch),
+        }
+
+        if !ids_match(&request.id, &response.id) {
+            return Err(Error::NonceMismatch);
+        }
+
+        Ok(response)
+    }
+
+    /// Sends a batch of requests to the server in a single round-trip,
+    /// returning the responses matched back up with the requests that
+    /// produced them, in the same order as `requests`.
+    ///
+    /// Per the JSON-RPC 2.0 spec, the server is free to return the
+    /// responses in any order and MUST NOT reply to notifications, so
+    /// each returned `Response` is paired with its request by `id` rather
+    /// than by position, and requests built with [`build_notification`]
+    /// contribute no entry to the result.
+    ///
+    /// [`build_notification`]: Client::build_notification
+    pub fn execute_batch<'a>(&self, requests: Vec<Request<'a>>) -> Result<Vec<Response>, Error> {
+        let responses = self.transport.send_batch(&requests)?;
+
+        // Json does not implement Hash, so key the map on the normalized
+        // id bytes; responses with no id at all cannot be matched to a
+        // particular request and are dropped.
+        let mut by_id: HashMap<Vec<u8>, Response> = responses
+            .into_iter()
+            .filter_map(|response| response.id.as_ref().map(normalize_id).map(|id| (id, response)))
+            .collect();
+
+        let mut ordered = Vec::with_capacity(requests.len());
+        for request in &requests {
+            let id = match request.id {
+                Some(ref id) => id,
+                None => continue,
+            };
+            match by_id.remove(&normalize_id(id)) {
+                Some(response) => ordered.push(response),
+                None => return Err(Error::NonceMismatch),
+            }
+        }
+
+        Ok(ordered)
+    }
+
+    /// Builds a request
+    pub fn build_request<N>(&self, name: N, params: Vec<Json>) -> Request<'static>
+    where
+        N: ToString,
+    {
+        let mut nonce = self.nonce.lock().unwrap();
+        *nonce += 1;
+
+        Request {
+            method: Cow::Owned(name.to_string()),
+            params: Cow::Owned(params),
+            id: Some(Json::from(*nonce)),
+            jsonrpc: Some(String::from("2.0"))
+        }
+    }
+
+    /// Builds a notification: a request with no `id`, which per the
+    /// JSON-RPC 2.0 spec the server MUST NOT reply to
+    pub fn build_notification<N>(&self, name: N, params: Vec<Json>) -> Request<'static>
+    where
+        N: ToString,
+    {
+        Request {
+            method: Cow::Owned(name.to_string()),
+            params: Cow::Owned(params),
+            id: None,
+            jsonrpc: Some(String::from("2.0"))
+        }
+    }
+
+    /// Sends a notification to the server, without waiting for or
+    /// validating a response
+    pub fn notify<'a>(&self, request: &Request<'a>) -> Result<(), Error> {
+        self.transport.send_notification(request)
+    }
+
+    /// Accessor for the last-used nonce
+    pub fn last_nonce(&self) -> u64 {
+        *self.nonce.lock().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use transport::HttpTransport;
+
+    #[test]
+    fn sanity() {
+        let client = Client::new("http://localhost", None, None);
+        assert_eq!(client.last_nonce(), 0);
+        let req1 = client.build_request("test".to_owned(), vec![]);
+        assert_eq!(client.last_nonce(), 1);
+        let req2 = client.build_request("test".to_owned(), vec![]);
+        assert_eq!(client.last_nonce(), 2);
+        assert!(req1 != req2);
+    }
+
+    #[test]
+    fn with_transport() {
+        let client = Client::with_transport(HttpTransport::new("http://localhost", None, None));
+        assert_eq!(client.last_nonce(), 0);
+    }
+
+    #[test]
+    fn batch_serializes_as_a_single_json_array() {
+        let client = Client::new("http://localhost", None, None);
+        let requests = vec![
+            client.build_request("test".to_owned(), vec![]),
+            client.build_request("test".to_owned(), vec![]),
+        ];
+
+        let body = Json::from_serialize(&requests).unwrap().to_bytes();
+        assert_eq!(body[0], b'[');
+    }
+}
@@ -0,0 +1,187 @@
+// Rust JSON-RPC Library
+// Written in 2015 by
+//     Andrew Poelstra <apoelstra@wpsoftware.net>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+//! # Error handling
+
+use std::borrow::Cow;
+use std::error;
+use std::fmt;
+use std::io;
+
+use reqwest;
+use strason::Json;
+
+/// A library error
+#[derive(Debug)]
+pub enum Error {
+    /// A error response was received from the server
+    Rpc(RpcError<'static>),
+    /// Response has neither error nor result
+    NoErrorOrResult,
+    /// Response to a request did not have the expected nonce
+    NonceMismatch,
+    /// Response to a request had a jsonrpc field other than "2.0"
+    VersionMismatch,
+    /// Json error
+    Json(strason::Error),
+    /// Error from the underlying HTTP transport
+    Reqwest(reqwest::Error),
+    /// A persistent-connection transport (e.g. WebSocket) lost its
+    /// connection to the server
+    ConnectionClosed,
+    /// A transport that matches requests to responses by `id` was asked
+    /// to wait for a reply to a request with no `id` (i.e. a notification)
+    MissingId,
+    /// I/O error from a local transport (e.g. a Unix domain socket)
+    Io(io::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Rpc(ref e) => write!(f, "server returned error {}: {}", e.code, e.message),
+            Error::NoErrorOrResult => write!(f, "response has neither error nor result"),
+            Error::NonceMismatch => write!(f, "nonce of response did not match nonce of request"),
+            Error::VersionMismatch => write!(f, "`jsonrpc` field set to non-\"2.0\""),
+            Error::Json(ref e) => write!(f, "JSON error: {}", e),
+            Error::Reqwest(ref e) => write!(f, "HTTP error: {}", e),
+            Error::ConnectionClosed => write!(f, "connection to server was closed"),
+            Error::MissingId => write!(f, "cannot wait for a reply to a request with no id"),
+            Error::Io(ref e) => write!(f, "I/O error: {}", e),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn description(&self) -> &'static str {
+        "jsonrpc error"
+    }
+
+    fn cause(&self) -> Option<&error::Error> {
+        match *self {
+            Error::Json(ref e) => Some(e),
+            Error::Reqwest(ref e) => Some(e),
+            Error::Io(ref e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<strason::Error> for Error {
+    fn from(e: strason::Error) -> Error {
+        Error::Json(e)
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(e: reqwest::Error) -> Error {
+        Error::Reqwest(e)
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Error {
+        Error::Io(e)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+/// A JSONRPC error object
+///
+/// The message can borrow from a `&str` when the error is built by hand;
+/// call [`into_owned`][RpcError::into_owned] to detach it from that
+/// borrow. Deserializing always produces an owned message, so an error
+/// parsed out of a response never borrows from the response buffer.
+pub struct RpcError<'a> {
+    /// Standard error code
+    pub code: i32,
+    /// Short description of the error
+    pub message: Cow<'a, str>,
+    /// Additional information about the error
+    pub data: Option<Json>,
+}
+
+/// The common case of an [`RpcError`] that owns its message outright
+pub type OwnedRpcError = RpcError<'static>;
+
+impl<'a> RpcError<'a> {
+    /// Classifies `self.code` against the JSON-RPC 2.0 reserved error
+    /// codes, so callers can match on well-known failures instead of
+    /// comparing raw integers
+    pub fn reason(&self) -> JsonRpcErrorReason {
+        match self.code {
+            -32700 => JsonRpcErrorReason::ParseError,
+            -32600 => JsonRpcErrorReason::InvalidRequest,
+            -32601 => JsonRpcErrorReason::MethodNotFound,
+            -32602 => JsonRpcErrorReason::InvalidParams,
+            -32603 => JsonRpcErrorReason::InternalError,
+            code @ -32099..=-32000 => JsonRpcErrorReason::ServerError(code),
+            code => JsonRpcErrorReason::Other(code),
+        }
+    }
+
+    /// Clones the message if it was borrowed, so the error no longer
+    /// depends on the lifetime of whatever it was built from
+    pub fn into_owned(self) -> RpcError<'static> {
+        RpcError {
+            code: self.code,
+            message: Cow::Owned(self.message.into_owned()),
+            data: self.data,
+        }
+    }
+}
+
+/// The reserved JSON-RPC 2.0 error codes, and the ranges they fall into
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum JsonRpcErrorReason {
+    /// Invalid JSON was received by the server (`-32700`)
+    ParseError,
+    /// The JSON sent is not a valid Request object (`-32600`)
+    InvalidRequest,
+    /// The method does not exist or is not available (`-32601`)
+    MethodNotFound,
+    /// Invalid method parameter(s) (`-32602`)
+    InvalidParams,
+    /// Internal JSON-RPC error (`-32603`)
+    InternalError,
+    /// Reserved for implementation-defined server errors (`-32000` to
+    /// `-32099`)
+    ServerError(i32),
+    /// Any other, application-defined error code
+    Other(i32),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{JsonRpcErrorReason, RpcError};
+
+    fn error(code: i32) -> RpcError<'static> {
+        RpcError {
+            code: code,
+            message: "test".into(),
+            data: None,
+        }
+    }
+
+    #[test]
+    fn reason_classifies_reserved_codes() {
+        assert_eq!(error(-32700).reason(), JsonRpcErrorReason::ParseError);
+        assert_eq!(error(-32600).reason(), JsonRpcErrorReason::InvalidRequest);
+        assert_eq!(error(-32601).reason(), JsonRpcErrorReason::MethodNotFound);
+        assert_eq!(error(-32602).reason(), JsonRpcErrorReason::InvalidParams);
+        assert_eq!(error(-32603).reason(), JsonRpcErrorReason::InternalError);
+        assert_eq!(error(-32050).reason(), JsonRpcErrorReason::ServerError(-32050));
+        assert_eq!(error(-1).reason(), JsonRpcErrorReason::Other(-1));
+    }
+}
@@ -29,36 +29,20 @@
 #![deny(unused_mut)]
 #![warn(missing_docs)]
 
-extern crate hyper;
+extern crate reqwest;
+#[cfg(feature = "ws")]
+extern crate ws;
 
 extern crate serde;
 #[macro_use]
 extern crate serde_derive;
 extern crate strason;
 
-#[cfg(feature = "unstable")]
-extern crate reqwest;
-
 pub mod client;
 pub mod error;
+pub mod transport;
 
-#[cfg(feature = "unstable")]
-mod reqwest_client;
-
-/// A set of unstable functionality.
-///
-/// This module is only available when the `unstable` [feature][1] is enabled.
-/// There is no backwards compatibility guarantee for any of the types within.
-///
-/// [1]: http://doc.crates.io/specifying-dependencies.html#choosing-features
-#[cfg(feature = "unstable")]
-pub mod unstable {
-    /// This is the implementation of the `Client` with reqwest instead of
-    /// using hyper.
-    pub mod client {
-        pub use reqwest_client::Client;
-    }
-}
+use std::borrow::Cow;
 
 use strason::Json;
 // Re-export error type
@@ -66,30 +50,82 @@ pub use error::Error;
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 /// A JSONRPC request object
-pub struct Request {
+///
+/// Borrows its method name and parameters where possible, so that a
+/// request built from data that is already in scope (e.g. a `&str`
+/// literal and a slice of `Json` already held by the caller) does not
+/// need to allocate. Call [`into_owned`][Request::into_owned] to detach
+/// it from whatever it was borrowed from.
+pub struct Request<'a> {
     /// The name of the RPC call
-    pub method: String,
+    pub method: Cow<'a, str>,
     /// Parameters to the RPC call
-    pub params: Vec<Json>,
-    /// Identifier for this Request, which should appear in the response
-    pub id: Json,
+    pub params: Cow<'a, [Json]>,
+    /// Identifier for this Request, which should appear in the response.
+    /// A notification is a Request with no `id`, which servers MUST NOT
+    /// reply to; it is omitted from the serialized object rather than
+    /// sent as `null`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<Json>,
     /// jsonrpc field, MUST be "2.0"
     pub jsonrpc: Option<String>
 }
 
+/// The common case of a [`Request`] that owns its method name and params
+/// outright
+pub type OwnedRequest = Request<'static>;
+
+impl<'a> Request<'a> {
+    /// Clones the method name and params if they were borrowed, so the
+    /// request no longer depends on the lifetime of whatever it was
+    /// built from
+    pub fn into_owned(self) -> Request<'static> {
+        Request {
+            method: Cow::Owned(self.method.into_owned()),
+            params: Cow::Owned(self.params.into_owned()),
+            id: self.id,
+            jsonrpc: self.jsonrpc
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 /// A JSONRPC response object
 pub struct Response {
     /// A result if there is one, or null
     pub result: Option<Json>,
     /// An error if there is one, or null
-    pub error: Option<error::RpcError>,
-    /// Identifier for this Request, which should match that of the request
-    pub id: Json,
+    pub error: Option<error::RpcError<'static>>,
+    /// Identifier for this Request, which should match that of the request.
+    /// Lenient servers sometimes send `null` or omit this entirely (e.g.
+    /// when the request itself could not be parsed), so it is optional
+    /// rather than required.
+    pub id: Option<Json>,
     /// jsonrpc field, MUST be "2.0"
     pub jsonrpc: Option<String>
 }
 
+/// Normalizes a JSONRPC id for comparison, tolerating servers that echo a
+/// numeric id back as a string or vice versa
+pub(crate) fn normalize_id(id: &Json) -> Vec<u8> {
+    if let Ok(s) = id.clone().into_deserialize::<String>() {
+        s.into_bytes()
+    } else if let Ok(n) = id.clone().into_deserialize::<i64>() {
+        n.to_string().into_bytes()
+    } else {
+        id.to_bytes()
+    }
+}
+
+/// Compares two JSONRPC ids for equality using [`normalize_id`]
+pub(crate) fn ids_match(a: &Option<Json>, b: &Option<Json>) -> bool {
+    match (a, b) {
+        (&Some(ref a), &Some(ref b)) => normalize_id(a) == normalize_id(b),
+        (&None, &None) => true,
+        _ => false,
+    }
+}
+
 impl Response {
     /// Extract the result from a response
     pub fn result<T: serde::de::DeserializeOwned>(&self) -> Result<T, Error> {
@@ -129,6 +165,8 @@ impl Response {
 
 #[cfg(test)]
 mod tests {
+    use std::borrow::Cow;
+
     use super::{Request, Response};
     use super::error::RpcError;
     use strason::Json;
@@ -136,12 +174,12 @@ mod tests {
     #[test]
     fn request_serialize_round_trip() {
         let original = Request {
-            method: "test".to_owned(),
+            method: "test".into(),
             params: vec![From::from(()),
                          From::from(false),
                          From::from(true),
-                         From::from("test2")],
-            id: From::from("69"),
+                         From::from("test2")].into(),
+            id: Some(From::from("69")),
             jsonrpc: Some(String::from("2.0"))
         };
 
@@ -151,11 +189,42 @@ mod tests {
         assert_eq!(original, des);
     }
 
+    #[test]
+    fn notification_omits_id() {
+        let notification = Request {
+            method: "test".into(),
+            params: vec![].into(),
+            id: None,
+            jsonrpc: Some(String::from("2.0"))
+        };
+
+        let ser = Json::from_serialize(&notification).unwrap();
+        let des: Request = ser.into_deserialize().unwrap();
+
+        assert_eq!(notification, des);
+        assert_eq!(des.id, None);
+    }
+
+    #[test]
+    fn request_into_owned() {
+        let borrowed_method: Cow<str> = Cow::Borrowed("test");
+        let borrowed_params: Cow<[Json]> = Cow::Borrowed(&[]);
+        let request = Request {
+            method: borrowed_method,
+            params: borrowed_params,
+            id: None,
+            jsonrpc: Some(String::from("2.0"))
+        };
+
+        let owned: Request<'static> = request.into_owned();
+        assert_eq!(owned.method, "test");
+    }
+
     #[test]
     fn response_serialize_round_trip() {
         let original_err = RpcError {
             code: -77,
-            message: "test4".to_owned(),
+            message: "test4".into(),
             data: Some(From::from(true))
         };
 
@@ -165,7 +234,7 @@ mod tests {
                                                  From::from(true),
                                                  From::from("test2")])),
             error: Some(original_err),
-            id: From::from(101),
+            id: Some(From::from(101)),
             jsonrpc: Some(String::from("2.0"))
         };
 
@@ -180,14 +249,14 @@ mod tests {
         let joanna = Response {
             result: Some(From::from(true)),
             error: None,
-            id: From::from(81),
+            id: Some(From::from(81)),
             jsonrpc: Some(String::from("2.0"))
         };
 
         let bill = Response {
             result: None,
             error: None,
-            id: From::from(66),
+            id: Some(From::from(66)),
             jsonrpc: Some(String::from("2.0"))
         };
 
@@ -201,7 +270,7 @@ mod tests {
         let response = Response {
             result: Some(Json::from_serialize(&obj).unwrap()),
             error: None,
-            id: From::from(()),
+            id: Some(From::from(())),
             jsonrpc: Some(String::from("2.0"))
         };
         let recovered1: Vec<String> = response.result().unwrap();
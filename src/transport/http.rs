@@ -0,0 +1,116 @@
+// Rust JSON-RPC Library
+// Written in 2015 by
+//     Andrew Poelstra <apoelstra@wpsoftware.net>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+use reqwest::{Client as ReqwestClient, Request as ReqwestRequest, Method, Url};
+use reqwest::header::{Headers, Authorization, Basic};
+use strason::Json;
+
+use error::Error;
+use transport::Transport;
+use {Request, Response};
+
+/// A transport that sends requests to a server over HTTP, via a single
+/// POST per call (or per batch)
+pub struct HttpTransport {
+    url: Url,
+    user: Option<String>,
+    pass: Option<String>,
+    client: ReqwestClient,
+}
+
+impl HttpTransport {
+    /// Creates a new HTTP transport
+    pub fn new<U, P>(url: &str, user: U, pass: P) -> HttpTransport
+    where
+        U: Into<Option<String>>,
+        P: Into<Option<String>>,
+    {
+        let (user, pass) = (user.into(), pass.into());
+        // Check that if we have a password, we have a username; other way around is ok
+        debug_assert!(pass.is_none() || user.is_some());
+
+        HttpTransport {
+            url: Url::parse(url).unwrap(),
+            user: user,
+            pass: pass,
+            client: ReqwestClient::new(),
+        }
+    }
+
+    fn headers(&self) -> Headers {
+        let mut headers = Headers::new();
+        if let Some(ref user) = self.user {
+            headers.set(Authorization(Basic {
+                username: user.clone(),
+                password: self.pass.clone(),
+            }));
+        }
+        headers
+    }
+
+    fn build_reqwest_request(&self, body: Vec<u8>) -> ReqwestRequest {
+        let mut reqwest_request = ReqwestRequest::new(Method::Post, self.url.clone());
+        *(reqwest_request.headers_mut()) = self.headers();
+        *(reqwest_request.body_mut()) = Some(body.into());
+        reqwest_request
+    }
+
+    fn execute(&self, body: Vec<u8>) -> Result<::reqwest::Response, Error> {
+        Ok(self.client.execute(self.build_reqwest_request(body))?)
+    }
+
+    fn post(&self, body: Vec<u8>) -> Result<Json, Error> {
+        let mut stream = self.execute(body)?;
+
+        // nb we ignore stream.status since we expect the body
+        // to contain information about any error
+        Ok(Json::from_reader(&mut stream)?)
+    }
+}
+
+impl Transport for HttpTransport {
+    fn send_request<'a>(&self, request: &Request<'a>) -> Result<Response, Error> {
+        let body = Json::from_serialize(request)?.to_bytes();
+        Ok(self.post(body)?.into_deserialize()?)
+    }
+
+    fn send_batch<'a>(&self, requests: &[Request<'a>]) -> Result<Vec<Response>, Error> {
+        let body = Json::from_serialize(requests)?.to_bytes();
+        Ok(self.post(body)?.into_deserialize()?)
+    }
+
+    fn send_notification<'a>(&self, request: &Request<'a>) -> Result<(), Error> {
+        let body = Json::from_serialize(request)?.to_bytes();
+        self.execute(body)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sanity() {
+        let transport = HttpTransport::new("http://localhost", None, None);
+        assert!(transport.user.is_none());
+    }
+
+    #[test]
+    fn requests_are_posted() {
+        let transport = HttpTransport::new("http://localhost", None, None);
+        let request = transport.build_reqwest_request(b"[]".to_vec());
+        assert_eq!(*request.method(), Method::Post);
+    }
+}
@@ -0,0 +1,51 @@
+// Rust JSON-RPC Library
+// Written in 2015 by
+//     Andrew Poelstra <apoelstra@wpsoftware.net>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+//! # Transports
+//!
+//! A [`Transport`] is responsible for carrying serialized JSONRPC requests
+//! to a server and parsing its responses; everything else (nonce
+//! bookkeeping, matching responses back up with their requests) is handled
+//! by [`Client`][::client::Client], which is generic over the transport in
+//! use. This keeps that logic shared between every way of reaching a
+//! server, rather than duplicated per transport.
+
+mod http;
+#[cfg(unix)]
+mod unix;
+#[cfg(feature = "ws")]
+mod ws;
+
+pub use self::http::HttpTransport;
+#[cfg(unix)]
+pub use self::unix::{Framing, UnixSocketTransport};
+#[cfg(feature = "ws")]
+pub use self::ws::WsTransport;
+
+use error::Error;
+use {Request, Response};
+
+/// Carries JSONRPC requests to a server and returns its responses
+pub trait Transport {
+    /// Sends a single request and waits for the matching response
+    fn send_request<'a>(&self, request: &Request<'a>) -> Result<Response, Error>;
+
+    /// Sends a batch of requests in a single round-trip and returns the
+    /// responses the server sent back, in whatever order it sent them
+    fn send_batch<'a>(&self, requests: &[Request<'a>]) -> Result<Vec<Response>, Error>;
+
+    /// Sends a notification (a request with no `id`) without waiting for
+    /// or validating a response, since the server MUST NOT send one
+    fn send_notification<'a>(&self, request: &Request<'a>) -> Result<(), Error>;
+}
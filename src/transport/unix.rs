@@ -0,0 +1,166 @@
+// Rust JSON-RPC Library
+// Written in 2015 by
+//     Andrew Poelstra <apoelstra@wpsoftware.net>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::os::unix::net::UnixStream;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use strason::Json;
+
+use error::Error;
+use transport::Transport;
+use {Request, Response};
+
+/// How messages are delimited on the wire
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Framing {
+    /// Each JSON value is terminated by a single `\n`, as used by
+    /// c-lightning
+    Newline,
+    /// Each JSON value is preceded by a `Content-Length: <n>\r\n\r\n`
+    /// header, as used by the Language Server Protocol
+    ContentLength,
+}
+
+/// A transport that talks JSONRPC over a Unix domain socket, as exposed by
+/// local daemons such as c-lightning
+pub struct UnixSocketTransport {
+    path: PathBuf,
+    framing: Framing,
+    stream: Mutex<Option<BufReader<UnixStream>>>,
+}
+
+impl UnixSocketTransport {
+    /// Creates a transport for a daemon listening on the Unix socket at
+    /// `path`, framing each message with a trailing newline. The socket is
+    /// not opened until the first message is sent.
+    pub fn new<P: Into<PathBuf>>(path: P) -> UnixSocketTransport {
+        UnixSocketTransport::with_framing(path, Framing::Newline)
+    }
+
+    /// Creates a transport for a daemon listening on the Unix socket at
+    /// `path`, using the given message framing. The socket is not opened
+    /// until the first message is sent.
+    pub fn with_framing<P: Into<PathBuf>>(path: P, framing: Framing) -> UnixSocketTransport {
+        UnixSocketTransport {
+            path: path.into(),
+            framing: framing,
+            stream: Mutex::new(None),
+        }
+    }
+
+    fn connect(&self) -> Result<BufReader<UnixStream>, Error> {
+        Ok(BufReader::new(UnixStream::connect(&self.path)?))
+    }
+
+    fn write_message(&self, stream: &mut BufReader<UnixStream>, body: &[u8]) -> Result<(), Error> {
+        // Writes bypass the reader's buffer entirely (it only buffers
+        // reads), so this can't step on bytes `read_message` has already
+        // buffered ahead of a frame boundary.
+        let writer = stream.get_mut();
+        match self.framing {
+            Framing::Newline => {
+                writer.write_all(body)?;
+                writer.write_all(b"\n")?;
+            }
+            Framing::ContentLength => {
+                write!(writer, "Content-Length: {}\r\n\r\n", body.len())?;
+                writer.write_all(body)?;
+            }
+        }
+        Ok(writer.flush()?)
+    }
+
+    fn read_message(&self, reader: &mut BufReader<UnixStream>) -> Result<Json, Error> {
+        match self.framing {
+            Framing::Newline => {
+                let mut line = String::new();
+                reader.read_line(&mut line)?;
+                Ok(Json::from_reader(&mut line.as_bytes())?)
+            }
+            Framing::ContentLength => {
+                let mut content_length = 0;
+                loop {
+                    let mut header = String::new();
+                    reader.read_line(&mut header)?;
+                    let header = header.trim();
+                    if header.is_empty() {
+                        break;
+                    }
+                    if header.starts_with("Content-Length:") {
+                        content_length = header["Content-Length:".len()..].trim().parse().unwrap_or(0);
+                    }
+                }
+                let mut body = vec![0u8; content_length];
+                reader.read_exact(&mut body)?;
+                Ok(Json::from_reader(&mut &body[..])?)
+            }
+        }
+    }
+
+    /// Sends a single serialized request and reads back one framed message
+    fn roundtrip(&self, body: Vec<u8>) -> Result<Json, Error> {
+        let mut guard = self.stream.lock().unwrap();
+        if guard.is_none() {
+            *guard = Some(self.connect()?);
+        }
+
+        // The daemon may have closed an idle connection; reconnect once
+        // and retry before giving up. Only an I/O failure indicates a
+        // dead connection worth retrying on — a parse error means the
+        // daemon replied with something we couldn't understand, and
+        // re-sending a non-idempotent request (e.g. a payment) in that
+        // case would be wrong, so those are propagated as-is.
+        let first_try = {
+            let stream = guard.as_mut().unwrap();
+            self.write_message(stream, &body)
+                .and_then(|_| self.read_message(stream))
+        };
+
+        match first_try {
+            Ok(json) => Ok(json),
+            Err(Error::Io(_)) => {
+                let mut stream = self.connect()?;
+                self.write_message(&mut stream, &body)?;
+                let json = self.read_message(&mut stream)?;
+                *guard = Some(stream);
+                Ok(json)
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
+
+impl Transport for UnixSocketTransport {
+    fn send_request<'a>(&self, request: &Request<'a>) -> Result<Response, Error> {
+        let body = Json::from_serialize(request)?.to_bytes();
+        Ok(self.roundtrip(body)?.into_deserialize()?)
+    }
+
+    fn send_batch<'a>(&self, requests: &[Request<'a>]) -> Result<Vec<Response>, Error> {
+        let body = Json::from_serialize(requests)?.to_bytes();
+        Ok(self.roundtrip(body)?.into_deserialize()?)
+    }
+
+    fn send_notification<'a>(&self, request: &Request<'a>) -> Result<(), Error> {
+        let body = Json::from_serialize(request)?.to_bytes();
+        let mut guard = self.stream.lock().unwrap();
+        if guard.is_none() {
+            *guard = Some(self.connect()?);
+        }
+        let stream = guard.as_mut().unwrap();
+        self.write_message(stream, &body)
+    }
+}
@@ -0,0 +1,235 @@
+// Rust JSON-RPC Library
+// Written in 2015 by
+//     Andrew Poelstra <apoelstra@wpsoftware.net>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use strason::Json;
+use ws::{self, CloseCode, Handler, Handshake, Message, Sender as WsSender};
+
+use error::Error;
+use transport::Transport;
+use {normalize_id, Request, Response};
+
+/// How long to wait before trying to reconnect after the connection drops
+const RECONNECT_DELAY: Duration = Duration::from_millis(500);
+
+struct Pending {
+    /// The request as originally sent, kept around so it can be re-issued
+    /// if the connection drops before a response arrives
+    request: Json,
+    reply: mpsc::Sender<Response>,
+}
+
+struct Shared {
+    url: String,
+    sender: Mutex<Option<WsSender>>,
+    pending: Mutex<HashMap<Vec<u8>, Pending>>,
+    /// Set once the owning `WsTransport` is dropped, so the reconnect
+    /// loop knows to stop instead of chasing the server forever
+    closing: AtomicBool,
+}
+
+impl Shared {
+    fn send_text(&self, text: String) -> Result<(), Error> {
+        match *self.sender.lock().unwrap() {
+            Some(ref sender) => sender
+                .send(Message::text(text))
+                .map_err(|_| Error::ConnectionClosed),
+            None => Err(Error::ConnectionClosed),
+        }
+    }
+
+    fn complete(&self, response: Response) {
+        // A response with no id at all cannot be matched to a waiting
+        // caller over a multiplexed connection, so it is dropped.
+        let id = match response.id {
+            Some(ref id) => normalize_id(id),
+            None => return,
+        };
+        if let Some(pending) = self.pending.lock().unwrap().remove(&id) {
+            let _ = pending.reply.send(response);
+        }
+    }
+
+    fn dispatch(&self, json: Json) {
+        if let Ok(response) = json.clone().into_deserialize::<Response>() {
+            self.complete(response);
+            return;
+        }
+        if let Ok(responses) = json.into_deserialize::<Vec<Response>>() {
+            for response in responses {
+                self.complete(response);
+            }
+        }
+    }
+}
+
+/// A `ws::Handler` that relays inbound frames to the waiting callers and,
+/// on (re)connect, flushes any requests that are still waiting for a
+/// response
+struct Relay {
+    shared: Arc<Shared>,
+}
+
+impl Handler for Relay {
+    fn on_open(&mut self, _: Handshake) -> ws::Result<()> {
+        let pending = self.shared.pending.lock().unwrap();
+        for pending in pending.values() {
+            let _ = self
+                .shared
+                .send_text(String::from_utf8(pending.request.to_bytes()).unwrap());
+        }
+        Ok(())
+    }
+
+    fn on_message(&mut self, msg: Message) -> ws::Result<()> {
+        if let Ok(text) = msg.into_text() {
+            if let Ok(json) = Json::from_reader(&mut text.as_bytes()) {
+                self.shared.dispatch(json);
+            }
+        }
+        Ok(())
+    }
+
+    fn on_close(&mut self, _: CloseCode, _: &str) {
+        *self.shared.sender.lock().unwrap() = None;
+    }
+}
+
+/// A transport that keeps a persistent WebSocket connection open to the
+/// server. Requests are matched back up with their responses by `id` as
+/// they arrive on a background reader thread; if the connection drops,
+/// it is transparently reconnected and any requests still awaiting a
+/// response are re-issued.
+pub struct WsTransport {
+    shared: Arc<Shared>,
+}
+
+impl WsTransport {
+    /// Connects to a server at the given `ws://` or `wss://` URL
+    pub fn new(url: &str) -> WsTransport {
+        let shared = Arc::new(Shared {
+            url: url.to_owned(),
+            sender: Mutex::new(None),
+            pending: Mutex::new(HashMap::new()),
+            closing: AtomicBool::new(false),
+        });
+
+        let reconnect_shared = shared.clone();
+        thread::spawn(move || {
+            while !reconnect_shared.closing.load(Ordering::Relaxed) {
+                let connect_shared = reconnect_shared.clone();
+                let _ = ws::connect(reconnect_shared.url.clone(), move |out| {
+                    *connect_shared.sender.lock().unwrap() = Some(out);
+                    Relay {
+                        shared: connect_shared.clone(),
+                    }
+                });
+                *reconnect_shared.sender.lock().unwrap() = None;
+                if reconnect_shared.closing.load(Ordering::Relaxed) {
+                    break;
+                }
+                thread::sleep(RECONNECT_DELAY);
+            }
+        });
+
+        WsTransport { shared: shared }
+    }
+
+    /// Registers a pending reply and writes `request` to the socket. Only
+    /// valid for requests that carry an `id`; use [`Shared::send_text`]
+    /// directly for notifications.
+    fn call<'a>(&self, request: &Request<'a>) -> Result<mpsc::Receiver<Response>, Error> {
+        let id = match request.id {
+            Some(ref id) => normalize_id(id),
+            None => return Err(Error::MissingId),
+        };
+
+        let (tx, rx) = mpsc::channel();
+        let body = Json::from_serialize(request)?;
+        self.shared
+            .pending
+            .lock()
+            .unwrap()
+            .insert(id, Pending { request: body.clone(), reply: tx });
+        // If this fails because we are mid-reconnect, the request stays
+        // registered and `Relay::on_open` will send it once we are back up.
+        let _ = self.shared.send_text(String::from_utf8(body.to_bytes()).unwrap());
+        Ok(rx)
+    }
+}
+
+impl Transport for WsTransport {
+    fn send_request<'a>(&self, request: &Request<'a>) -> Result<Response, Error> {
+        self.call(request)?.recv().map_err(|_| Error::ConnectionClosed)
+    }
+
+    fn send_batch<'a>(&self, requests: &[Request<'a>]) -> Result<Vec<Response>, Error> {
+        // Serialize every request before registering any of them, so a
+        // failure partway through doesn't leave earlier entries stuck in
+        // `pending` forever (nothing would ever arrive to clear them).
+        let mut entries = Vec::with_capacity(requests.len());
+        for request in requests {
+            let id = match request.id {
+                Some(ref id) => normalize_id(id),
+                None => continue,
+            };
+            entries.push((id, Json::from_serialize(request)?));
+        }
+
+        let mut receivers = Vec::with_capacity(entries.len());
+        {
+            let mut pending = self.shared.pending.lock().unwrap();
+            for &(ref id, ref body) in &entries {
+                let (tx, rx) = mpsc::channel();
+                pending.insert(id.clone(), Pending { request: body.clone(), reply: tx });
+                receivers.push(rx);
+            }
+        }
+        for &(_, ref body) in &entries {
+            // If this fails because we are mid-reconnect, the request
+            // stays registered and `Relay::on_open` sends it once we are
+            // back up.
+            let _ = self.shared.send_text(String::from_utf8(body.to_bytes()).unwrap());
+        }
+
+        receivers
+            .into_iter()
+            .map(|rx| rx.recv().map_err(|_| Error::ConnectionClosed))
+            .collect()
+    }
+
+    fn send_notification<'a>(&self, request: &Request<'a>) -> Result<(), Error> {
+        let body = Json::from_serialize(request)?;
+        self.shared.send_text(String::from_utf8(body.to_bytes()).unwrap())
+    }
+}
+
+impl Drop for WsTransport {
+    fn drop(&mut self) {
+        // Tell the reconnect loop to stop, then close the live connection
+        // (if any) so the blocking `ws::connect` call it's sitting in
+        // returns immediately instead of leaving the loop to reconnect
+        // forever after this transport is gone.
+        self.shared.closing.store(true, Ordering::Relaxed);
+        if let Some(sender) = self.shared.sender.lock().unwrap().take() {
+            let _ = sender.close(CloseCode::Normal);
+        }
+    }
+}